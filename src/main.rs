@@ -1,25 +1,158 @@
 use egg_mode::{
+    entities::{MediaEntity, MediaType, UrlEntity, VideoInfo},
     tweet::{DraftTweet, Tweet},
     user::TwitterUser,
-    KeyPair, Response, Token,
+    KeyPair, Token,
 };
 use env_logger::Env;
 use futures::TryStreamExt;
-use log::{error, info, trace};
-use serde::Deserialize;
+use log::{error, info, trace, warn};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use serenity::{
     async_trait,
     builder::CreateMessage,
-    model::{channel::Embed, gateway::Ready, id::ChannelId, prelude::Message},
+    model::{
+        channel::{Embed, Reaction},
+        gateway::Ready,
+        id::ChannelId,
+        prelude::Message,
+    },
     prelude::*,
 };
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-/// TwitterConfig represents all the configuration required for Twitter.
+/// Base delay used for the first reconnect attempt after a stream drops.
+const STREAM_RECONNECT_BASE_MS: u64 = 250;
+/// Upper bound the exponential reconnect backoff is capped at.
+const STREAM_RECONNECT_MAX_MS: u64 = 120_000;
+/// How long a stream has to stay healthy before the backoff is reset to its base delay.
+const STREAM_HEALTHY_THRESHOLD: Duration = Duration::from_secs(60);
+/// How long a posted tweet id is remembered for before it's pruned from the seen-tweet cache.
+const SEEN_CACHE_WINDOW: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// How long a fetched reply/quote tweet body is kept around before it's re-fetched.
+const TWEET_BODY_CACHE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Current time as Unix seconds, used to timestamp cache entries.
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// TimedCache is a small HashMap-backed cache keyed by tweet id that evicts entries older than
+/// `window`. Used both for the seen-tweet dedup cache (value `()`) and for the reply/quote tweet
+/// body cache (value `String`), so reconnects or repeated fetches don't grow memory forever.
+struct TimedCache<T> {
+    window: Duration,
+    entries: HashMap<u64, (u64, T)>,
+}
+
+impl<T> TimedCache<T> {
+    fn new(window: Duration) -> TimedCache<T> {
+        TimedCache {
+            window,
+            entries: HashMap::new(),
+        }
+    }
+
+    fn contains(&self, id: u64) -> bool {
+        self.entries.contains_key(&id)
+    }
+
+    fn get(&self, id: u64) -> Option<&T> {
+        self.entries.get(&id).map(|(_, value)| value)
+    }
+
+    fn insert(&mut self, id: u64, value: T) {
+        self.entries.insert(id, (now_unix(), value));
+        self.prune();
+    }
+
+    fn prune(&mut self) {
+        let cutoff = now_unix().saturating_sub(self.window.as_secs());
+        self.entries.retain(|_, (seen_at, _)| *seen_at >= cutoff);
+    }
+}
+
+/// SeenCache dedups tweets already posted to Discord, guarding against stream reconnects,
+/// retweets of a watched account surfacing twice, or boundary overlap redelivering the same
+/// tweet. Backed by a JSON file at `path` so the dedup survives restarts.
+struct SeenCache {
+    path: String,
+    cache: TimedCache<()>,
+}
+
+impl SeenCache {
+    /// Load a seen-tweet cache from `path`, starting empty if it doesn't exist or can't be read.
+    fn load(path: &str) -> SeenCache {
+        let entries = std::fs::File::open(path)
+            .ok()
+            .and_then(|f| serde_json::from_reader::<_, HashMap<u64, u64>>(f).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(id, seen_at)| (id, (seen_at, ())))
+            .collect();
+
+        SeenCache {
+            path: path.to_string(),
+            cache: TimedCache {
+                window: SEEN_CACHE_WINDOW,
+                entries,
+            },
+        }
+    }
+
+    fn contains(&self, id: u64) -> bool {
+        self.cache.contains(id)
+    }
+
+    /// Remember `id` as posted and persist the cache to disk.
+    fn insert(&mut self, id: u64) {
+        self.cache.insert(id, ());
+        self.persist();
+    }
+
+    fn persist(&self) {
+        let entries: HashMap<u64, u64> = self
+            .cache
+            .entries
+            .iter()
+            .map(|(id, (seen_at, _))| (*id, *seen_at))
+            .collect();
+
+        if let Ok(f) = std::fs::File::create(&self.path) {
+            if let Err(why) = serde_json::to_writer(f, &entries) {
+                error!("failed to persist seen-tweet cache: {:?}", why);
+            }
+        }
+    }
+}
+
+/// TwitterConfig represents all the configuration required for Twitter. `access_token` and
+/// `access_token_secret` are optional since they can be obtained through the interactive PIN
+/// based OAuth flow instead of being pre-minted and put in the configuration file. `follow` is a
+/// list of screen names; all of them are watched over a single stream connection and routed to
+/// their matching entry in `DiscordConfig::routes`.
 #[derive(Deserialize)]
 struct TwitterConfig {
-    follow: String,
+    follow: Vec<String>,
     consumer_key: String,
     consumer_secret: String,
+    #[serde(default)]
+    access_token: String,
+    #[serde(default)]
+    access_token_secret: String,
+}
+
+/// CachedToken is the sidecar file written next to the configuration file once a PIN based OAuth
+/// flow has been completed. This lets subsequent runs skip the interactive prompt.
+#[derive(Serialize, Deserialize)]
+struct CachedToken {
     access_token: String,
     access_token_secret: String,
 }
@@ -28,13 +161,43 @@ struct TwitterConfig {
 #[derive(Clone, Deserialize)]
 struct DiscordConfig {
     token: String,
+    /// Maps a reaction emoji to the Twitter action it should trigger on the reacted-to tweet.
+    /// Shared by every route, since reacting is a client-wide affordance.
+    reactions: Vec<ReactionAction>,
+    routes: Vec<DiscordRoute>,
+}
+
+/// DiscordRoute maps a single watched Twitter account (`follow`, matched against
+/// `TwitterConfig::follow`) to the Discord channel tweets from it are posted to, along with the
+/// formatting to use for that channel.
+#[derive(Clone, Deserialize)]
+struct DiscordRoute {
+    follow: String,
     channel_id: u64,
     tweet_replies: bool,
+    tweet_actions: bool,
     tweet_as_user: bool,
     embed: DiscordConfigEmbed,
     plaintext: DiscordConfigPlaintext,
 }
 
+/// ReactionAction maps a single reaction emoji to the egg_mode call it should trigger when a user
+/// reacts to (or removes a reaction from) one of the bot's mirrored tweet messages.
+#[derive(Clone, Deserialize)]
+struct ReactionAction {
+    emoji: String,
+    action: TweetAction,
+}
+
+/// TweetAction is the egg_mode call a reaction triggers. Adding a reaction performs the action,
+/// removing it undoes it.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum TweetAction {
+    Like,
+    Retweet,
+}
+
 #[derive(Clone, Deserialize)]
 struct DiscordConfigEmbed {
     header: String,
@@ -59,86 +222,242 @@ struct Config {
     discord: DiscordConfig,
 }
 
-/// TwitterService represents the service that knows about the user at Twitter to watch and the
-/// token to use when calling the APIs. The user field is a `TwitterUser` and can be used to show
-/// things such as display name and ID.
+/// TwitterService represents the service that knows about the users at Twitter to watch and the
+/// token to use when calling the APIs. `users` holds the resolved `TwitterUser` for every
+/// configured `follow` entry and can be used to show things such as display name and ID. `seen`
+/// dedups tweets already posted and `tweet_bodies` caches fetched reply/quote tweet text so it
+/// isn't re-fetched on every delivery.
 struct TwitterService {
-    user: Response<TwitterUser>,
+    users: Vec<TwitterUser>,
     token: Token,
+    seen: Mutex<SeenCache>,
+    tweet_bodies: Mutex<TimedCache<String>>,
 }
 
 impl TwitterService {
     /// Create a new TwitterService with the passed config. A token will be created with all the
-    /// credentials and with this token the user to watch will be fetched. This means that the new
-    /// constructor will fail if the credentials is wrong or if the user does not exist (or is
-    /// private and not followed).
-    async fn new(config: TwitterConfig) -> TwitterService {
+    /// credentials and with this token every user to watch will be fetched. This means that the
+    /// new constructor will fail if the credentials is wrong or if one of the users does not
+    /// exist (or is private and not followed).
+    ///
+    /// If `access_token`/`access_token_secret` are missing from the config (and no cached token
+    /// file is found next to `config_file`), the interactive PIN based OAuth flow is started:
+    /// the operator is given a URL to open and authorize the app at, and is asked to paste back
+    /// the PIN shown by Twitter. The resulting access token is then cached to disk so future
+    /// runs don't need to repeat the flow.
+    async fn new(config: TwitterConfig, config_file: &str) -> TwitterService {
         let con_token = KeyPair::new(config.consumer_key, config.consumer_secret);
-        let access_token = KeyPair::new(config.access_token, config.access_token_secret);
+        let token_cache_path = format!("{}.token", config_file);
+
+        let access_token = if !config.access_token.is_empty() && !config.access_token_secret.is_empty() {
+            KeyPair::new(config.access_token, config.access_token_secret)
+        } else if let Some(cached) = Self::load_cached_token(&token_cache_path) {
+            KeyPair::new(cached.access_token, cached.access_token_secret)
+        } else {
+            Self::pin_oauth_flow(&con_token, &token_cache_path).await
+        };
+
         let token = Token::Access {
             consumer: con_token,
             access: access_token,
         };
 
-        let user = egg_mode::user::show(config.follow, &token).await.unwrap();
+        let mut users = Vec::with_capacity(config.follow.len());
+        for screen_name in config.follow {
+            let user = egg_mode::user::show(screen_name, &token).await.unwrap();
+            users.push(user.response);
+        }
+
+        let seen = Mutex::new(SeenCache::load(&format!("{}.seen.json", config_file)));
+        let tweet_bodies = Mutex::new(TimedCache::new(TWEET_BODY_CACHE_WINDOW));
 
-        TwitterService { user, token }
+        TwitterService {
+            users,
+            token,
+            seen,
+            tweet_bodies,
+        }
     }
 
-    /// Stream the feed with everything coming from the watched user. The context and channel ID
-    /// passed comes from the Discord ready handler so this can be used when sending tweets to
-    /// Discord.
-    async fn stream(&self, ctx: Context, config: &DiscordConfig) {
-        let mut stream = egg_mode::stream::filter()
-            .follow(&[self.user.id])
-            .start(&self.token);
+    /// Look up a previously resolved user by its Twitter screen name, case-insensitively.
+    fn find_user_by_name(&self, screen_name: &str) -> Option<&TwitterUser> {
+        self.users
+            .iter()
+            .find(|u| u.screen_name.eq_ignore_ascii_case(screen_name))
+    }
 
-        info!("starting stream, watching {}", self.user.name);
+    /// Fetch the text of tweet `id`, serving it from `tweet_bodies` when available instead of
+    /// hitting the API again. Used for the reply/quote body shown alongside a mirrored tweet.
+    async fn cached_tweet_text(&self, id: u64) -> Option<String> {
+        if let Some(text) = self.tweet_bodies.lock().unwrap().get(id) {
+            return Some(text.clone());
+        }
 
-        while let Ok(m) = stream.try_next().await {
-            if let Some(egg_mode::stream::StreamMessage::Tweet(tweet)) = m {
-                trace!("tweet received in stream");
-                self.handle_message(&ctx, &config, tweet).await;
+        match egg_mode::tweet::show(id, &self.token).await {
+            Ok(t) => {
+                let (full_text, urls) = Self::full_text_and_entities(&t);
+                let text = Self::expand_urls(full_text, urls);
+                self.tweet_bodies.lock().unwrap().insert(id, text.clone());
+                Some(text)
+            }
+            Err(why) => {
+                error!("failed to fetch tweet {}: {:?}", id, why);
+                None
             }
         }
     }
 
-    /// Handle the message that got received in the Twitter stream. If the tweet follows required
-    /// criterias, an embedded message will be constructed and posted to those channels configured.
-    async fn handle_message(&self, ctx: &Context, config: &DiscordConfig, tweet: Tweet) {
-        let tweeting_user = tweet.user.as_ref().unwrap();
+    /// Read a previously cached access token from `path`, if present.
+    fn load_cached_token(path: &str) -> Option<CachedToken> {
+        let f = std::fs::File::open(path).ok()?;
+        serde_yaml::from_reader(f).ok()
+    }
+
+    /// Run the three-legged PIN based OAuth flow: obtain a request token, print the authorize
+    /// URL for the operator to visit, read the PIN they're given back from stdin, and exchange
+    /// it for an access token. The resulting token is persisted to `token_cache_path` so this
+    /// flow doesn't need to run again on the next start.
+    async fn pin_oauth_flow(con_token: &KeyPair, token_cache_path: &str) -> KeyPair {
+        let request_token = egg_mode::auth::request_token(con_token, "oob")
+            .await
+            .expect("failed to obtain a request token");
+
+        let authorize_url = egg_mode::auth::authorize_url(&request_token);
+
+        println!("Go to the following URL, authorize the app and paste the PIN shown below:");
+        println!("{}", authorize_url);
+        print!("PIN: ");
+        std::io::stdout().flush().ok();
+
+        let mut pin = String::new();
+        std::io::stdin()
+            .read_line(&mut pin)
+            .expect("failed to read PIN from stdin");
+
+        let (token, _user_id, screen_name) =
+            egg_mode::auth::access_token(con_token.clone(), &request_token, pin.trim())
+                .await
+                .expect("failed to exchange PIN for an access token");
+
+        let access = match token {
+            Token::Access { access, .. } => access,
+            Token::Bearer(_) => panic!("expected an access token, got a bearer token"),
+        };
+
+        info!("authorized as @{}", screen_name);
 
-        if tweeting_user.id != self.user.id {
-            trace!(
-                "Tweet matched filter but was from {}, not {}. Will not post",
-                tweeting_user.screen_name,
-                self.user.screen_name
-            );
+        let cached = CachedToken {
+            access_token: access.key.to_string(),
+            access_token_secret: access.secret.to_string(),
+        };
+
+        if let Ok(f) = std::fs::File::create(token_cache_path) {
+            if let Err(why) = serde_yaml::to_writer(f, &cached) {
+                error!("failed to cache access token: {:?}", why);
+            }
+        }
+
+        access
+    }
+
+    /// Stream the feed with everything coming from every watched user. The context and routing
+    /// table passed comes from the Discord ready handler so this can be used when sending tweets
+    /// to Discord.
+    ///
+    /// This is a long-lived, supervising loop: whenever the underlying stream errors out or
+    /// ends, it's rebuilt and retried with an exponential backoff (with jitter, capped at
+    /// `STREAM_RECONNECT_MAX_MS`) instead of letting the bot go silently deaf. The backoff is
+    /// reset to its base delay once a connection has stayed healthy for `STREAM_HEALTHY_THRESHOLD`.
+    async fn stream(&self, ctx: Context, routes: &[DiscordRoute]) {
+        let mut backoff_ms = STREAM_RECONNECT_BASE_MS;
+        let ids: Vec<u64> = self.users.iter().map(|u| u.id).collect();
+        let names: Vec<&str> = self.users.iter().map(|u| u.name.as_str()).collect();
+
+        loop {
+            info!("starting stream, watching {}", names.join(", "));
+
+            let mut stream = egg_mode::stream::filter().follow(&ids).start(&self.token);
+            let connected_at = Instant::now();
+
+            loop {
+                match stream.try_next().await {
+                    Ok(Some(egg_mode::stream::StreamMessage::Tweet(tweet))) => {
+                        trace!("tweet received in stream");
+                        self.handle_message(&ctx, routes, tweet).await;
+                    }
+                    Ok(Some(_)) => {}
+                    Ok(None) => {
+                        warn!("stream ended, reconnecting");
+                        break;
+                    }
+                    Err(why) => {
+                        warn!("stream error: {:?}, reconnecting", why);
+                        break;
+                    }
+                }
+
+                if connected_at.elapsed() >= STREAM_HEALTHY_THRESHOLD {
+                    backoff_ms = STREAM_RECONNECT_BASE_MS;
+                }
+            }
+
+            let jitter = rand::thread_rng().gen_range(0..=backoff_ms / 2);
+            let delay = backoff_ms + jitter;
+
+            info!("reconnecting to stream in {}ms", delay);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+
+            backoff_ms = (backoff_ms * 2).min(STREAM_RECONNECT_MAX_MS);
+        }
+    }
+
+    /// Handle the message that got received in the Twitter stream. The tweeting user's id is
+    /// matched against `routes` (via each route's `follow` screen name) to find the channel and
+    /// formatting to post with; tweets from users with no configured route are ignored. Tweets
+    /// already posted (per the `seen` cache) are skipped, guarding against stream reconnects or
+    /// overlapping deliveries reposting the same tweet.
+    async fn handle_message(&self, ctx: &Context, routes: &[DiscordRoute], tweet: Tweet) {
+        if self.seen.lock().unwrap().contains(tweet.id) {
+            trace!("tweet {} already posted, skipping", tweet.id);
             return;
         }
 
+        let tweeting_user = tweet.user.as_ref().unwrap();
+
+        let route = match routes.iter().find(|r| {
+            self.find_user_by_name(&r.follow)
+                .map_or(false, |u| u.id == tweeting_user.id)
+        }) {
+            Some(route) => route,
+            None => {
+                trace!(
+                    "Tweet matched filter but {} has no configured route. Will not post",
+                    tweeting_user.screen_name
+                );
+                return;
+            }
+        };
+
+        let tweet_id = tweet.id;
         let tweet_url = format!(
             "https://twitter.com/{}/status/{}",
-            self.user.screen_name, tweet.id
+            tweeting_user.screen_name, tweet.id
         );
 
-        trace!("@{}: {} ({})", self.user.screen_name, tweet.text, tweet_url);
+        trace!("@{}: {} ({})", tweeting_user.screen_name, tweet.text, tweet_url);
 
         // Since the embed closure isn't async we fetch the tweet replied to if this is a reply.
         let reply = match tweet.in_reply_to_status_id {
-            Some(reply_id) => {
-                let reply_tweet = egg_mode::tweet::show(reply_id, &self.token).await.unwrap();
-                Some(reply_tweet.text.clone())
-            }
+            Some(reply_id) => self.cached_tweet_text(reply_id).await,
             None => None,
         };
 
-        let result = ChannelId(config.channel_id)
+        let result = ChannelId(route.channel_id)
             .send_message(ctx, |m| {
-                if config.tweet_as_user {
-                    self.create_plaintext_message(m, &config, tweet, reply);
+                if route.tweet_as_user {
+                    self.create_plaintext_message(m, route, tweet, tweet_url, reply);
                 } else {
-                    self.create_embeded_message(m, &config, tweet, tweet_url, reply);
+                    self.create_embeded_message(m, route, tweet, tweet_url, reply);
                 }
 
                 m
@@ -148,20 +467,33 @@ impl TwitterService {
         if let Err(why) = result {
             error!("error sending message: {:?}", why);
         } else {
-            trace!("sent message to {} successfully", config.channel_id);
+            // Only mark the tweet as seen once it's actually been posted, so a failed send (rate
+            // limit, network blip, Discord outage) can still be retried by a later redelivery
+            // instead of being dropped forever.
+            self.seen.lock().unwrap().insert(tweet_id);
+            trace!("sent message to {} successfully", route.channel_id);
         };
     }
 
     /// Create a plaintext message to write from the bot just like if it would've written what's
     /// happened on Twitter straight to the chat. This will make it appear a bit more like it's a
     /// human writing the post.
+    ///
+    /// The tweet URL is appended as a trailing link wrapped in `<...>` so Discord doesn't unfurl
+    /// it into its own embed; `tweet_id_from_message` relies on this line to recover the tweet a
+    /// plaintext message mirrors when a reply or reaction comes back on it.
     fn create_plaintext_message(
         &self,
         m: &mut CreateMessage,
-        config: &DiscordConfig,
+        config: &DiscordRoute,
         tweet: Tweet,
+        tweet_url: String,
         reply: Option<String>,
     ) {
+        let (media_url, main_image, extra_images, video_urls) = Self::extract_media(&tweet);
+        let (full_text, urls) = Self::full_text_and_entities(&tweet);
+        let text = Self::expand_urls(full_text, urls);
+
         let mut content = "".to_string();
         if let Some(r) = reply {
             content.push_str(format!("{}\n", &config.plaintext.reply_prefix).as_str());
@@ -170,12 +502,19 @@ impl TwitterService {
         }
 
         if let Some(q) = tweet.quoted_status {
+            let quote_text = Self::expand_urls(&q.text, &q.entities.urls);
             content.push_str(format!("{}\n", &config.plaintext.quote_prefix).as_str());
-            content.push_str(format!("> {}\n", q.text).as_str());
+            content.push_str(format!("> {}\n", quote_text).as_str());
             content.push_str(format!("{}\n", &config.plaintext.quote_postfix).as_str());
         }
 
-        content.push_str(tweet.text.as_str());
+        content.push_str(Self::strip_media_url(&text, &media_url).as_str());
+
+        for url in video_urls.iter().chain(main_image.iter()).chain(extra_images.iter()) {
+            content.push_str(format!("\n{}", url).as_str());
+        }
+
+        content.push_str(format!("\n<{}>", tweet_url).as_str());
 
         m.content(content);
     }
@@ -185,27 +524,146 @@ impl TwitterService {
     fn create_embeded_message(
         &self,
         m: &mut CreateMessage,
-        config: &DiscordConfig,
+        config: &DiscordRoute,
         tweet: Tweet,
         tweet_url: String,
         reply: Option<String>,
     ) {
+        let (media_url, main_image, extra_images, video_urls) = Self::extract_media(&tweet);
+        let (full_text, urls) = Self::full_text_and_entities(&tweet);
+        let expanded_text = Self::expand_urls(full_text, urls);
+        let text = Self::strip_media_url(&expanded_text, &media_url);
+
         m.embed(|e| {
             e.title(&config.embed.header);
-            e.field(&config.embed.text, tweet.text, false);
+            e.field(&config.embed.text, text, false);
 
             if let Some(r) = reply {
                 e.field(&config.embed.reply, r, false);
             }
 
             if let Some(q) = tweet.quoted_status {
-                e.field(&config.embed.quote, q.text, false);
+                let quote_text = Self::expand_urls(&q.text, &q.entities.urls);
+                e.field(&config.embed.quote, quote_text, false);
+            }
+
+            if let Some(url) = &main_image {
+                e.image(url);
             }
 
             e.field(&config.embed.url, tweet_url, false);
 
             e
         });
+
+        // Discord only renders one image per embed, so any remaining photos are posted as
+        // additional embeds on the same message.
+        for url in &extra_images {
+            m.add_embed(|e| e.image(url));
+        }
+
+        // Videos and GIFs can't be set as an embed image; posting the raw URL in the message
+        // content makes Discord unfurl a playable player instead.
+        if !video_urls.is_empty() {
+            m.content(video_urls.join("\n"));
+        }
+    }
+
+    /// Pull the media attached to a tweet, preferring the richer `extended_entities` over
+    /// `entities.media` when present. Returns the `t.co` URL to strip from the tweet text (shared
+    /// by every attachment), the first photo to use as the embed's main image, any remaining
+    /// photos, and the highest-bitrate variant URL for any videos/GIFs.
+    fn extract_media(tweet: &Tweet) -> (Option<String>, Option<String>, Vec<String>, Vec<String>) {
+        let media: &[MediaEntity] = tweet
+            .extended_entities
+            .as_ref()
+            .map(|e| e.media.as_slice())
+            .or_else(|| tweet.entities.media.as_deref())
+            .unwrap_or(&[]);
+
+        let mut media_url = None;
+        let mut main_image = None;
+        let mut extra_images = Vec::new();
+        let mut video_urls = Vec::new();
+
+        for entity in media {
+            if media_url.is_none() {
+                media_url = Some(entity.url.clone());
+            }
+
+            match entity.media_type {
+                MediaType::Photo => {
+                    if main_image.is_none() {
+                        main_image = Some(entity.media_url_https.clone());
+                    } else {
+                        extra_images.push(entity.media_url_https.clone());
+                    }
+                }
+                MediaType::Video | MediaType::Gif => {
+                    if let Some(url) = entity.video_info.as_ref().and_then(Self::best_video_variant) {
+                        video_urls.push(url);
+                    }
+                }
+            }
+        }
+
+        (media_url, main_image, extra_images, video_urls)
+    }
+
+    /// Pick the highest-bitrate `video/mp4` variant from a media entity's video info, skipping
+    /// non-video content types such as the HLS manifest egg_mode also exposes.
+    fn best_video_variant(info: &VideoInfo) -> Option<String> {
+        info.variants
+            .iter()
+            .filter(|v| v.content_type == "video/mp4")
+            .max_by_key(|v| v.bitrate.unwrap_or(0))
+            .map(|v| v.url.clone())
+    }
+
+    /// Remove the trailing `t.co` media URL from a tweet's text so the link isn't duplicated
+    /// alongside the image/video Discord renders from the embed or message content.
+    fn strip_media_url(text: &str, media_url: &Option<String>) -> String {
+        match media_url {
+            Some(url) => text.replace(url.as_str(), "").trim().to_string(),
+            None => text.to_string(),
+        }
+    }
+
+    /// Replace every `t.co` short URL in `text` with its expanded form, so mirrored posts show
+    /// readable, clickable links instead of opaque shortlinks.
+    fn expand_urls(text: &str, urls: &[UrlEntity]) -> String {
+        Self::replace_shortlinks(text, urls.iter().map(|url| (url.url.as_str(), url.expanded_url.as_str())))
+    }
+
+    /// Substitution pass behind `expand_urls`, split out so the replacement order and collision
+    /// behavior (an expansion containing another entity's short URL as a substring) can be unit
+    /// tested without a real `UrlEntity`.
+    fn replace_shortlinks<'a>(text: &str, replacements: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+        let mut expanded = text.to_string();
+
+        for (short, long) in replacements {
+            expanded = expanded.replace(short, long);
+        }
+
+        expanded
+    }
+
+    /// A retweet's own `text`/`entities` are truncated to make room for the "RT @user: " prefix,
+    /// cutting the body off mid-sentence; the untruncated text lives on `retweeted_status`
+    /// instead. Prefer that full-length body (and its entities) when a tweet is a retweet.
+    fn full_text_and_entities(tweet: &Tweet) -> (&str, &[UrlEntity]) {
+        let retweeted = tweet
+            .retweeted_status
+            .as_ref()
+            .map(|r| (r.text.as_str(), r.entities.urls.as_slice()));
+
+        Self::pick_full_text((tweet.text.as_str(), tweet.entities.urls.as_slice()), retweeted)
+    }
+
+    /// Selection logic behind `full_text_and_entities`, split out so "prefer the retweeted body
+    /// over the truncated wrapper, when there is one" can be unit tested without a real `Tweet`.
+    fn pick_full_text<'a, T>(own: (&'a str, T), retweeted: Option<(&'a str, T)>) -> (&'a str, T) {
+        retweeted.unwrap_or(own)
     }
 }
 
@@ -217,25 +675,105 @@ struct Handler {
 
 impl Handler {
     /// Check all embedded contents and for each one check every field. If the field is named
-    /// what's configured as the URL, try to extract the Tweet ID (last part) and return it as u64.
-    fn tweet_id_from_embeds(&self, embeds: &[Embed]) -> Result<u64, std::io::ErrorKind> {
+    /// what's configured as the URL for a route matching `predicate`, extract the screen name
+    /// and Tweet ID from the tweet URL it holds.
+    fn tweet_id_from_embeds(
+        &self,
+        embeds: &[Embed],
+        predicate: impl Fn(&DiscordRoute) -> bool,
+    ) -> Result<(String, u64), std::io::ErrorKind> {
         for embed in embeds {
             for field in &embed.fields {
-                if field.name == self.config.embed.url {
-                    return Ok(std::path::Path::new(&field.value)
-                        .file_name()
-                        .ok_or(std::io::ErrorKind::InvalidInput)?
-                        .to_str()
-                        .ok_or(std::io::ErrorKind::InvalidInput)?
-                        .parse::<u64>()
-                        .or(Err(std::io::ErrorKind::InvalidData)))?;
+                let matched_route = self
+                    .config
+                    .routes
+                    .iter()
+                    .find(|r| predicate(r) && field.name == r.embed.url);
+
+                if matched_route.is_none() {
+                    continue;
                 }
+
+                let path = std::path::Path::new(&field.value);
+
+                let id = path
+                    .file_name()
+                    .ok_or(std::io::ErrorKind::InvalidInput)?
+                    .to_str()
+                    .ok_or(std::io::ErrorKind::InvalidInput)?
+                    .parse::<u64>()
+                    .or(Err(std::io::ErrorKind::InvalidData))?;
+
+                let screen_name = path
+                    .parent()
+                    .and_then(|p| p.parent())
+                    .and_then(|p| p.file_name())
+                    .ok_or(std::io::ErrorKind::InvalidInput)?
+                    .to_str()
+                    .ok_or(std::io::ErrorKind::InvalidInput)?
+                    .to_string();
+
+                return Ok((screen_name, id));
+            }
+        }
+
+        Err(std::io::ErrorKind::NotFound)
+    }
+
+    /// `tweet_as_user` routes post plaintext content instead of an embed, so `tweet_id_from_embeds`
+    /// never finds anything for them. Fall back to the trailing `<https://twitter.com/.../status/id>`
+    /// link `create_plaintext_message` appends, matching the screen name against a route satisfying
+    /// `predicate` the same way the embed path matches on field name.
+    fn tweet_id_from_content(
+        &self,
+        content: &str,
+        predicate: impl Fn(&DiscordRoute) -> bool,
+    ) -> Result<(String, u64), std::io::ErrorKind> {
+        for line in content.lines().rev() {
+            let line = line.trim().trim_start_matches('<').trim_end_matches('>');
+
+            let rest = match line.strip_prefix("https://twitter.com/") {
+                Some(rest) => rest,
+                None => continue,
+            };
+
+            let mut parts = rest.splitn(3, '/');
+            let screen_name = parts.next().ok_or(std::io::ErrorKind::InvalidInput)?;
+            let segment = parts.next().ok_or(std::io::ErrorKind::InvalidInput)?;
+            let id = parts.next().ok_or(std::io::ErrorKind::InvalidInput)?;
+
+            if segment != "status" {
+                continue;
             }
+
+            let matched_route = self
+                .config
+                .routes
+                .iter()
+                .any(|r| predicate(r) && r.follow.eq_ignore_ascii_case(screen_name));
+
+            if !matched_route {
+                continue;
+            }
+
+            let id = id.parse::<u64>().or(Err(std::io::ErrorKind::InvalidData))?;
+            return Ok((screen_name.to_string(), id));
         }
 
         Err(std::io::ErrorKind::NotFound)
     }
 
+    /// Recover the tweet a message mirrors, checking its embeds first and falling back to its
+    /// plaintext content for `tweet_as_user` routes, which have no embed to inspect.
+    fn tweet_id_from_message(
+        &self,
+        message: &Message,
+        predicate: impl Fn(&DiscordRoute) -> bool + Copy,
+    ) -> Result<(String, u64), std::io::ErrorKind> {
+        self.tweet_id_from_embeds(&message.embeds, predicate)
+            .or_else(|_| self.tweet_id_from_content(&message.content, predicate))
+    }
+
     /// Parse a Discord message containing a tweet. If it's a reply to the bot itself, check if
     /// it's a message with embedded data and if it's possible to extract a tweet ID. If this is
     /// possible, send the reply to the tweet.
@@ -244,12 +782,10 @@ impl Handler {
             return;
         }
 
-        if let Ok(tweet_id) = self.tweet_id_from_embeds(&reply.embeds) {
-            let draft = DraftTweet::new(format!(
-                "@{} {}",
-                self.twitter_service.user.screen_name, msg.content
-            ))
-            .in_reply_to(tweet_id);
+        if let Ok((screen_name, tweet_id)) = self.tweet_id_from_message(reply, |r| r.tweet_replies)
+        {
+            let draft =
+                DraftTweet::new(format!("@{} {}", screen_name, msg.content)).in_reply_to(tweet_id);
 
             let tweet = draft.send(&self.twitter_service.token).await;
 
@@ -270,25 +806,97 @@ impl Handler {
             }
         }
     }
+
+    /// Handle a reaction being added to (`removed = false`) or removed from (`removed = true`) a
+    /// message the bot posted. If the reaction's emoji is configured in `DiscordConfig::reactions`
+    /// and the message is one of ours mirroring a tweet from a `tweet_actions`-enabled route, run
+    /// the matching egg_mode action (or its inverse) against that tweet and acknowledge the result
+    /// in the channel.
+    async fn handle_reaction(&self, ctx: Context, reaction: Reaction, removed: bool) {
+        let action = match self
+            .config
+            .reactions
+            .iter()
+            .find(|r| r.emoji == reaction.emoji.to_string())
+        {
+            Some(r) => r.action,
+            None => return,
+        };
+
+        let message = match reaction.message(&ctx).await {
+            Ok(m) => m,
+            Err(why) => {
+                error!("failed to fetch reacted-to message: {:?}", why);
+                return;
+            }
+        };
+
+        if message.author.id != ctx.cache.current_user_id().await {
+            return;
+        }
+
+        let tweet_id = match self.tweet_id_from_message(&message, |r| r.tweet_actions) {
+            Ok((_, tweet_id)) => tweet_id,
+            Err(_) => return,
+        };
+
+        let token = &self.twitter_service.token;
+        let result = match (action, removed) {
+            (TweetAction::Like, false) => egg_mode::tweet::like(tweet_id, token).await.map(|_| ()),
+            (TweetAction::Like, true) => egg_mode::tweet::unlike(tweet_id, token).await.map(|_| ()),
+            (TweetAction::Retweet, false) => {
+                egg_mode::tweet::retweet(tweet_id, token).await.map(|_| ())
+            }
+            (TweetAction::Retweet, true) => egg_mode::tweet::unretweet(tweet_id, token)
+                .await
+                .map(|_| ()),
+        };
+
+        match result {
+            Ok(_) => {
+                let verb = match (action, removed) {
+                    (TweetAction::Like, false) => "Liked",
+                    (TweetAction::Like, true) => "Unliked",
+                    (TweetAction::Retweet, false) => "Retweeted",
+                    (TweetAction::Retweet, true) => "Un-retweeted",
+                };
+
+                let _ = message
+                    .channel_id
+                    .say(&ctx, format!("{} tweet {}", verb, tweet_id))
+                    .await;
+            }
+            Err(why) => error!("failed to {:?} tweet {}: {:?}", action, tweet_id, why),
+        }
+    }
 }
 
 #[async_trait]
 impl EventHandler for Handler {
     /// We only implement ready since it's called whenever we successfully start the Discord
-    /// client. As soon as we're ready we start the twitter stream with the channel ID found on our
-    /// handler as channel destination.
+    /// client. As soon as we're ready we start the twitter stream with the routing table found on
+    /// our handler as channel destinations.
     async fn ready(&self, ctx: Context, _ready: Ready) {
-        self.twitter_service.stream(ctx, &self.config).await;
+        self.twitter_service.stream(ctx, &self.config.routes).await;
     }
 
     /// Check the message and see if it's a reply to ourself.
     async fn message(&self, ctx: Context, msg: Message) {
-        if self.config.tweet_replies {
-            if let Some(reply) = &msg.referenced_message {
-                self.reply_to_tweet(&reply, &ctx, &msg).await;
-            }
+        if let Some(reply) = &msg.referenced_message {
+            self.reply_to_tweet(&reply, &ctx, &msg).await;
         }
     }
+
+    /// A reaction was added to a message; if it's a configured action emoji on one of our tweet
+    /// messages, perform the matching Twitter action.
+    async fn reaction_add(&self, ctx: Context, add_reaction: Reaction) {
+        self.handle_reaction(ctx, add_reaction, false).await;
+    }
+
+    /// A reaction was removed from a message; undo the matching Twitter action if applicable.
+    async fn reaction_remove(&self, ctx: Context, removed_reaction: Reaction) {
+        self.handle_reaction(ctx, removed_reaction, true).await;
+    }
 }
 
 #[tokio::main]
@@ -304,7 +912,7 @@ async fn main() {
     let f = std::fs::File::open(config_file).unwrap();
     let config: Config = serde_yaml::from_reader(f).unwrap();
 
-    let twitter_service = TwitterService::new(config.twitter);
+    let twitter_service = TwitterService::new(config.twitter, config_file);
     let mut client = serenity::client::Client::builder(config.discord.token.as_str())
         .event_handler(Handler {
             twitter_service: twitter_service.await,
@@ -317,3 +925,110 @@ async fn main() {
         error!("client error: {:?}", why);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timed_cache_prune_evicts_entries_older_than_window() {
+        let mut cache: TimedCache<()> = TimedCache::new(Duration::from_secs(60));
+        cache.entries.insert(1, (now_unix() - 120, ()));
+        cache.entries.insert(2, (now_unix(), ()));
+
+        cache.prune();
+
+        assert!(!cache.contains(1));
+        assert!(cache.contains(2));
+    }
+
+    #[test]
+    fn timed_cache_insert_prunes_stale_entries() {
+        let mut cache: TimedCache<()> = TimedCache::new(Duration::from_secs(60));
+        cache.entries.insert(1, (now_unix() - 120, ()));
+
+        cache.insert(2, ());
+
+        assert!(!cache.contains(1));
+        assert!(cache.contains(2));
+    }
+
+    #[test]
+    fn seen_cache_persists_and_reloads_across_restarts() {
+        let path = std::env::temp_dir().join(format!(
+            "tweets-to-discord-test-seen-{}.json",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let mut cache = SeenCache::load(&path);
+        assert!(!cache.contains(42));
+
+        cache.insert(42);
+        assert!(cache.contains(42));
+
+        let reloaded = SeenCache::load(&path);
+        assert!(reloaded.contains(42));
+        assert!(!reloaded.contains(7));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn replace_shortlinks_expands_every_match() {
+        let text = "check this out: https://t.co/abc and also https://t.co/def";
+        let expanded = TwitterService::replace_shortlinks(
+            text,
+            vec![
+                ("https://t.co/abc", "https://example.com/one"),
+                ("https://t.co/def", "https://example.com/two"),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(
+            expanded,
+            "check this out: https://example.com/one and also https://example.com/two"
+        );
+    }
+
+    #[test]
+    fn replace_shortlinks_handles_expansion_containing_another_shortlink() {
+        // If an earlier expansion's replacement text happens to contain a later short URL as a
+        // substring, that later entity must still only replace its own original occurrence, not
+        // get mangled by the first substitution.
+        let text = "https://t.co/a https://t.co/b";
+        let expanded = TwitterService::replace_shortlinks(
+            text,
+            vec![
+                ("https://t.co/a", "see https://t.co/b for details"),
+                ("https://t.co/b", "https://example.com/real"),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(
+            expanded,
+            "see https://example.com/real for details https://example.com/real"
+        );
+    }
+
+    #[test]
+    fn pick_full_text_prefers_retweeted_body_when_present() {
+        let own = ("RT @user: truncat…", &[1, 2][..]);
+        let retweeted = Some(("the full untruncated body", &[3][..]));
+
+        assert_eq!(
+            TwitterService::pick_full_text(own, retweeted),
+            ("the full untruncated body", &[3][..])
+        );
+    }
+
+    #[test]
+    fn pick_full_text_falls_back_to_own_body_when_not_a_retweet() {
+        let own = ("just a regular tweet", &[1][..]);
+
+        assert_eq!(TwitterService::pick_full_text(own, None), own);
+    }
+}